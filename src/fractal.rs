@@ -0,0 +1,73 @@
+//! Multi-octave fractal synthesis, summing fractional Brownian motion
+//! across geometrically-spaced scales.
+
+use probability::generator::Generator;
+
+use gaussian::Motion;
+
+/// A multi-octave signal built by summing a fractional Brownian motion
+/// at several geometrically-spaced scales, analogous to the octave
+/// summation used to build Brownian/Perlin noise.
+pub struct Fractal {
+    motion: Motion,
+    octaves: usize,
+    lacunarity: f64,
+    persistence: f64,
+}
+
+impl Fractal {
+    /// Create a fractal synthesizer summing `octaves` octaves of
+    /// fractional Brownian motion with the given Hurst exponent. Each
+    /// successive octave samples at a step divided by `lacunarity` and
+    /// contributes with an amplitude multiplied by `persistence`.
+    pub fn new(hurst: f64, octaves: usize, lacunarity: f64, persistence: f64) -> Fractal {
+        debug_assert!(octaves > 0);
+        debug_assert!(lacunarity > 0.0);
+        debug_assert!(persistence > 0.0);
+        Fractal {
+            motion: Motion::new(hurst),
+            octaves: octaves,
+            lacunarity: lacunarity,
+            persistence: persistence,
+        }
+    }
+
+    /// Generate a sample path combining all octaves, normalized by the
+    /// total weight `Σ persistence^k`.
+    pub fn sample<G>(&self, points: usize, step: f64, generator: &mut G) -> Vec<f64>
+        where G: Generator
+    {
+        let mut data = vec![0.0; points];
+        let mut total = 0.0;
+        for k in 0..self.octaves {
+            let weight = self.persistence.powi(k as i32);
+            let octave_step = step * self.lacunarity.powi(-(k as i32));
+            let octave = self.motion.sample(points, octave_step, generator);
+            for (value, sample) in data.iter_mut().zip(octave) {
+                *value += weight * sample;
+            }
+            total += weight;
+        }
+        if total > 0.0 {
+            for value in data.iter_mut() {
+                *value /= total;
+            }
+        }
+        data
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use probability::generator::Default;
+
+    use super::Fractal;
+
+    #[test]
+    fn sample_returns_the_requested_length() {
+        let fractal = Fractal::new(0.5, 4, 2.0, 0.5);
+        let mut generator = Default::new(42);
+        let path = fractal.sample(20, 1.0, &mut generator);
+        assert_eq!(path.len(), 20);
+    }
+}
@@ -0,0 +1,266 @@
+//! Cross-correlated multivariate fractional Gaussian processes.
+
+use probability::generator::Generator;
+
+use Stationary;
+use gaussian::Noise;
+
+/// Decompose a symmetric positive-definite correlation matrix `R` into
+/// its Cholesky factor `L`, such that `R = L L^T`.
+fn cholesky(correlation: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let d = correlation.len();
+    for i in 0..d {
+        debug_assert!(correlation[i].len() == d);
+        for j in 0..d {
+            debug_assert!((correlation[i][j] - correlation[j][i]).abs() < 1e-9,
+                          "the correlation matrix should be symmetric");
+        }
+    }
+    let mut l = vec![vec![0.0; d]; d];
+    for i in 0..d {
+        for j in 0..(i + 1) {
+            let mut sum = correlation[i][j];
+            for k in 0..j {
+                sum -= l[i][k] * l[j][k];
+            }
+            if i == j {
+                debug_assert!(sum > 0.0, "the correlation matrix should be symmetric positive-definite");
+                l[i][j] = sum.sqrt();
+            } else {
+                l[i][j] = sum / l[j][j];
+            }
+        }
+    }
+    l
+}
+
+/// Mix `d` independent paths into `d` correlated ones via `y_t = L x_t`,
+/// applied at every point in time.
+///
+/// `L` is the Cholesky factor of the unit-diagonal correlation matrix
+/// `R`, so it only reproduces `R` when applied to unit-variance inputs.
+/// The independent paths are not unit-variance in general -- `sigma`
+/// gives each component's own marginal standard deviation at every
+/// point in time -- so each path is rescaled to unit variance before
+/// mixing and the result is rescaled back by the same factor
+/// afterward, which preserves both the requested cross-correlation and
+/// each component's original marginal variance.
+fn mix(cholesky: &[Vec<f64>], paths: &[Vec<f64>], sigma: &[Vec<f64>]) -> Vec<Vec<f64>> {
+    let d = paths.len();
+    let points = paths.get(0).map_or(0, |path| path.len());
+    let mut data = vec![vec![0.0; points]; d];
+    for t in 0..points {
+        let x: Vec<f64> = (0..d).map(|j| {
+            let s = sigma[j][t];
+            if s > 0.0 { paths[j][t] / s } else { 0.0 }
+        }).collect();
+        for i in 0..d {
+            let y: f64 = (0..=i).map(|j| cholesky[i][j] * x[j]).sum();
+            data[i][t] = y * sigma[i][t];
+        }
+    }
+    data
+}
+
+/// Generate one correlated fractional Gaussian noise path per
+/// component, mixing the `noises`' independent draws through their
+/// `cholesky` factor.
+///
+/// Shared by `CorrelatedNoise::sample`, which returns these paths
+/// directly, and `CorrelatedMotion::sample`, which cumulatively sums
+/// them to build correlated fractional Brownian motions out of
+/// correlated fractional Gaussian increments.
+fn sample_increments<G>(noises: &[Noise], cholesky: &[Vec<f64>], points: usize, generator: &mut G)
+    -> Vec<Vec<f64>>
+    where G: Generator
+{
+    let paths: Vec<Vec<f64>> = noises.iter().map(|noise| noise.sample(points, generator)).collect();
+    let sigma: Vec<Vec<f64>> = noises.iter()
+        .map(|noise| vec![Stationary::cov(noise, 0).sqrt(); points])
+        .collect();
+    mix(cholesky, &paths, &sigma)
+}
+
+/// A set of cross-correlated fractional Gaussian noises.
+pub struct CorrelatedNoise {
+    noises: Vec<Noise>,
+    cholesky: Vec<Vec<f64>>,
+}
+
+impl CorrelatedNoise {
+    /// Create a set of cross-correlated fractional Gaussian noises.
+    ///
+    /// `hurst` gives the Hurst exponent of each component, `step` is the
+    /// time step shared across components, and `correlation` is the
+    /// instantaneous correlation matrix `R` of the components, which
+    /// should be symmetric positive-definite.
+    pub fn new(hurst: &[f64], step: f64, correlation: &[Vec<f64>]) -> CorrelatedNoise {
+        debug_assert!(correlation.len() == hurst.len());
+        CorrelatedNoise {
+            noises: hurst.iter().map(|&hurst| Noise::new(hurst, step)).collect(),
+            cholesky: cholesky(correlation),
+        }
+    }
+
+    /// Generate one correlated sample path per component.
+    pub fn sample<G>(&self, points: usize, generator: &mut G) -> Vec<Vec<f64>>
+        where G: Generator
+    {
+        sample_increments(&self.noises, &self.cholesky, points, generator)
+    }
+}
+
+/// A set of cross-correlated fractional Brownian motions.
+pub struct CorrelatedMotion {
+    hurst: Vec<f64>,
+    cholesky: Vec<Vec<f64>>,
+}
+
+impl CorrelatedMotion {
+    /// Create a set of cross-correlated fractional Brownian motions.
+    ///
+    /// `hurst` gives the Hurst exponent of each component, and
+    /// `correlation` is the instantaneous correlation matrix `R` of the
+    /// increments of the components, which should be symmetric
+    /// positive-definite.
+    pub fn new(hurst: &[f64], correlation: &[Vec<f64>]) -> CorrelatedMotion {
+        debug_assert!(correlation.len() == hurst.len());
+        CorrelatedMotion {
+            hurst: hurst.to_vec(),
+            cholesky: cholesky(correlation),
+        }
+    }
+
+    /// Generate one correlated sample path per component.
+    ///
+    /// Builds correlated fractional Gaussian increments via the same
+    /// `sample_increments` mixing used by `CorrelatedNoise`, one
+    /// component per Hurst exponent at the given `step`, and
+    /// cumulatively sums each correlated increment stream, mirroring how
+    /// plain `Motion::sample` builds on plain `Noise::sample`.
+    pub fn sample<G>(&self, points: usize, step: f64, generator: &mut G) -> Vec<Vec<f64>>
+        where G: Generator
+    {
+        let d = self.hurst.len();
+        match points {
+            0 => vec![vec![]; d],
+            1 => vec![vec![0.0]; d],
+            _ => {
+                let noises: Vec<Noise> = self.hurst.iter().map(|&hurst| Noise::new(hurst, step)).collect();
+                let increments = sample_increments(&noises, &self.cholesky, points - 1, generator);
+                increments.into_iter().map(|increment| {
+                    let mut data = vec![0.0];
+                    data.extend(increment);
+                    for i in 2..points {
+                        data[i] += data[i - 1];
+                    }
+                    data
+                }).collect()
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use probability::generator::Default;
+
+    use super::{CorrelatedMotion, CorrelatedNoise};
+
+    #[test]
+    fn sample_returns_one_path_per_component() {
+        let hurst = [0.5, 0.7];
+        let correlation = vec![vec![1.0, 0.6], vec![0.6, 1.0]];
+        let noise = CorrelatedNoise::new(&hurst, 1.0, &correlation);
+        let mut generator = Default::new(42);
+        let paths = noise.sample(10, &mut generator);
+        assert_eq!(paths.len(), hurst.len());
+        assert!(paths.iter().all(|path| path.len() == 10));
+    }
+
+    #[test]
+    fn sample_matches_target_correlation_with_differing_hurst() {
+        // With differing Hurst exponents, the components' marginal
+        // variances differ (`step^(2 * hurst)`), so this also exercises
+        // the per-component normalization in `mix`: a `step` of `1.0`
+        // would mask that entirely since `1.0.powf(_) == 1.0`.
+        let hurst = [0.5, 0.9];
+        let step = 0.1;
+        let target = 0.6;
+        let correlation = vec![vec![1.0, target], vec![target, 1.0]];
+        let noise = CorrelatedNoise::new(&hurst, step, &correlation);
+        let mut generator = Default::new(42);
+        let trials = 20_000;
+        let (mut sum1, mut sum2, mut sum11, mut sum22, mut sum12) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for _ in 0..trials {
+            let paths = noise.sample(2, &mut generator);
+            let (a, b) = (paths[0][1], paths[1][1]);
+            sum1 += a;
+            sum2 += b;
+            sum11 += a * a;
+            sum22 += b * b;
+            sum12 += a * b;
+        }
+        let n = trials as f64;
+        let (mean1, mean2) = (sum1 / n, sum2 / n);
+        let var1 = sum11 / n - mean1 * mean1;
+        let var2 = sum22 / n - mean2 * mean2;
+        let cov = sum12 / n - mean1 * mean2;
+        let rho = cov / (var1.sqrt() * var2.sqrt());
+        assert!((rho - target).abs() < 0.05, "rho = {}", rho);
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_rejects_asymmetric_correlation() {
+        let hurst = [0.5, 0.7];
+        let correlation = vec![vec![1.0, 0.6], vec![0.1, 1.0]];
+        CorrelatedNoise::new(&hurst, 1.0, &correlation);
+    }
+
+    #[test]
+    fn correlated_motion_sample_returns_one_path_per_component() {
+        let hurst = [0.5, 0.7];
+        let correlation = vec![vec![1.0, 0.6], vec![0.6, 1.0]];
+        let motion = CorrelatedMotion::new(&hurst, &correlation);
+        let mut generator = Default::new(42);
+        let paths = motion.sample(10, 0.1, &mut generator);
+        assert_eq!(paths.len(), hurst.len());
+        assert!(paths.iter().all(|path| path.len() == 10));
+        assert!(paths.iter().all(|path| path[0] == 0.0));
+    }
+
+    #[test]
+    fn correlated_motion_increments_match_target_correlation_with_differing_hurst() {
+        // The documented invariant is on the *increments*. Differing
+        // Hurst exponents is exactly the case that broke when `sample`
+        // mixed already-cumulated positions instead of increments: the
+        // induced increment correlation drifted away from `target` as
+        // `t` grew, so checking a later lag (`t = 5`) rather than the
+        // first one matters here.
+        let hurst = [0.5, 0.9];
+        let step = 0.1;
+        let target = 0.6;
+        let correlation = vec![vec![1.0, target], vec![target, 1.0]];
+        let motion = CorrelatedMotion::new(&hurst, &correlation);
+        let mut generator = Default::new(42);
+        let trials = 20_000;
+        let (mut sum1, mut sum2, mut sum11, mut sum22, mut sum12) = (0.0, 0.0, 0.0, 0.0, 0.0);
+        for _ in 0..trials {
+            let paths = motion.sample(6, step, &mut generator);
+            let (a, b) = (paths[0][5] - paths[0][4], paths[1][5] - paths[1][4]);
+            sum1 += a;
+            sum2 += b;
+            sum11 += a * a;
+            sum22 += b * b;
+            sum12 += a * b;
+        }
+        let n = trials as f64;
+        let (mean1, mean2) = (sum1 / n, sum2 / n);
+        let var1 = sum11 / n - mean1 * mean1;
+        let var2 = sum22 / n - mean2 * mean2;
+        let cov = sum12 / n - mean1 * mean2;
+        let rho = cov / (var1.sqrt() * var2.sqrt());
+        assert!((rho - target).abs() < 0.05, "rho = {}", rho);
+    }
+}
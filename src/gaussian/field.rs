@@ -0,0 +1,193 @@
+//! Fractional Gaussian fields on a two-dimensional grid.
+
+use complex::Complex;
+use probability::distribution::{Distribution, Gaussian};
+use probability::generator::Generator;
+use dft::{Operation, Plan, Transform};
+
+use {Process, Stationary};
+use gaussian::{embed, Embedding, Noise};
+
+/// An isotropic fractional Gaussian field on a two-dimensional grid.
+///
+/// `Field` is the two-dimensional analogue of `Noise`: it embeds the
+/// covariance of the field into a block-circulant-with-circulant-blocks
+/// (BCCB) matrix and samples it with a two-dimensional FFT, paralleling
+/// the one-dimensional Davies–Harte embedding used by `Noise`.
+pub struct Field {
+    hurst: f64,
+}
+
+impl Field {
+    /// Create a fractional Gaussian field.
+    #[inline]
+    pub fn new(hurst: f64) -> Field {
+        debug_assert!(hurst > 0.0 && hurst < 1.0);
+        Field { hurst: hurst }
+    }
+
+    /// Generate a sample field on an `n1 × n2` grid.
+    pub fn sample<G>(&self, n1: usize, n2: usize, step: f64, generator: &mut G) -> Vec<Vec<f64>>
+        where G: Generator
+    {
+        self.try_sample(n1, n2, step, generator).0
+    }
+
+    /// Generate a sample field, along with whether the underlying BCCB
+    /// embedding was exact or only approximate; see `gaussian::Embedding`.
+    pub fn try_sample<G>(&self, n1: usize, n2: usize, step: f64, generator: &mut G)
+        -> (Vec<Vec<f64>>, Embedding)
+        where G: Generator
+    {
+        if n1 == 0 || n2 == 0 {
+            return (vec![vec![]; n1], Embedding::Exact);
+        }
+        if n1 == 1 || n2 == 1 {
+            // With one of the dimensions degenerate, the radial distance
+            // `|h| = sqrt(h1^2 + h2^2)` reduces to the 1D lag `|h1|` (or
+            // `|h2|`), so the field is exactly a 1D `Noise` path.
+            let (path, embedding) = Noise::new(self.hurst, step).try_sample(n1 * n2, generator);
+            let field = if n1 == 1 {
+                vec![path]
+            } else {
+                path.into_iter().map(|value| vec![value]).collect()
+            };
+            return (field, embedding);
+        }
+        let (m1, m2, lambda, embedding) = self.eigenvalues(n1, n2, step);
+        let gaussian = Gaussian::new(0.0, 1.0);
+        let data = spectrum(&lambda, m1, m2, || gaussian.sample(generator));
+        let field = (0..n1).map(|i| (0..n2).map(|j| data[i][j].re()).collect()).collect();
+        (field, embedding)
+    }
+
+    /// Compute the eigenvalues of the minimal BCCB embedding of the
+    /// field covariance, enlarging it until every eigenvalue is
+    /// non-negative or `gaussian::embed`'s cap is hit.
+    fn eigenvalues(&self, n1: usize, n2: usize, step: f64) -> (usize, usize, Vec<Vec<f64>>, Embedding) {
+        let minimum1 = 2 * (n1 - 1);
+        let minimum2 = 2 * (n2 - 1);
+        let (factor, flat, embedding) = embed(|factor| {
+            let m1 = minimum1 * factor;
+            let m2 = minimum2 * factor;
+            let mut block = self.block(n1, n2, m1, m2, step);
+            fft2(&mut block, Operation::Forward);
+            block.into_iter().flat_map(|row| row.into_iter().map(|point| point.re())).collect()
+        });
+        let m1 = minimum1 * factor;
+        let m2 = minimum2 * factor;
+        let lambda = flat.chunks(m2).map(|row| row.to_vec()).collect();
+        (m1, m2, lambda, embedding)
+    }
+
+    /// Build the BCCB block of size `m1 × m2` embedding the covariance
+    /// of the field, wrapping each dimension independently.
+    fn block(&self, n1: usize, n2: usize, m1: usize, m2: usize, step: f64) -> Vec<Vec<Complex>> {
+        let increment = Increment { hurst: self.hurst, step: step };
+        let mut block = vec![vec![Complex::new(0.0, 0.0); m2]; m1];
+        for h1 in 0..n1 {
+            for h2 in 0..n2 {
+                let value = Stationary::cov(&increment, (h1 as i64, h2 as i64));
+                block[wrap(h1, m1)][wrap(h2, m2)] = Complex::new(value, 0.0);
+                block[wrap(m1 - h1, m1)][wrap(h2, m2)] = Complex::new(value, 0.0);
+                block[wrap(h1, m1)][wrap(m2 - h2, m2)] = Complex::new(value, 0.0);
+                block[wrap(m1 - h1, m1)][wrap(m2 - h2, m2)] = Complex::new(value, 0.0);
+            }
+        }
+        block
+    }
+}
+
+/// The isotropic fractional-increment covariance of a `Field`, as a
+/// stationary process over the 2D lag `(h1, h2)`, generalizing
+/// `Stationary`/`Process` of `Noise` to the radial distance `|h| =
+/// sqrt(h1^2 + h2^2)`.
+struct Increment {
+    hurst: f64,
+    step: f64,
+}
+
+impl Stationary for Increment {
+    type Index = (i64, i64);
+
+    fn cov(&self, (h1, h2): (i64, i64)) -> f64 {
+        let power = 2.0 * self.hurst;
+        let radius = ((h1 * h1 + h2 * h2) as f64).sqrt();
+        0.5 * self.step.powf(power) * ((radius + 1.0).powf(power) - 2.0 * radius.powf(power) +
+                                       (radius - 1.0).abs().powf(power))
+    }
+}
+
+impl Process for Increment {
+    type Index = (i64, i64);
+    type State = f64;
+
+    #[inline]
+    fn cov(&self, (t1, t2): (i64, i64), (s1, s2): (i64, i64)) -> f64 {
+        Stationary::cov(self, (t1 - s1, t2 - s2))
+    }
+}
+
+/// Wrap an index that may fall outside `[0, m)` back into range.
+fn wrap(index: usize, m: usize) -> usize {
+    index % m
+}
+
+/// Draw a complex Gaussian spectrum over a 2D eigenvalue grid and invert
+/// it via a 2D FFT to obtain a BCCB-embedded sample.
+fn spectrum<F>(lambda: &[Vec<f64>], m1: usize, m2: usize, mut gaussian: F) -> Vec<Vec<Complex>>
+    where F: FnMut() -> f64
+{
+    let scale = 1.0 / ((m1 * m2) as f64).sqrt();
+    let mut data: Vec<Vec<Complex>> = lambda.iter().map(|row| {
+        row.iter().map(|&value| {
+            let radius = scale * value.sqrt();
+            Complex::new(radius * gaussian(), radius * gaussian())
+        }).collect()
+    }).collect();
+    fft2(&mut data, Operation::Inverse);
+    data
+}
+
+/// Apply a 2D FFT (or its inverse) in place, by transforming each row
+/// and then each column.
+fn fft2(data: &mut Vec<Vec<Complex>>, operation: Operation) {
+    let m1 = data.len();
+    let m2 = data[0].len();
+    let plan2 = Plan::new(operation, m2);
+    for row in data.iter_mut() {
+        row.transform(&plan2);
+    }
+    let plan1 = Plan::new(operation, m1);
+    for j in 0..m2 {
+        let mut column: Vec<Complex> = (0..m1).map(|i| data[i][j]).collect();
+        column.transform(&plan1);
+        for (i, &value) in column.iter().enumerate() {
+            data[i][j] = value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use probability::generator::Default;
+
+    use super::Field;
+
+    #[test]
+    fn sample_handles_degenerate_grids() {
+        let field = Field::new(0.5);
+        let mut generator = Default::new(42);
+
+        assert_eq!(field.sample(0, 3, 1.0, &mut generator), Vec::<Vec<f64>>::new());
+        assert_eq!(field.sample(1, 0, 1.0, &mut generator), vec![vec![]]);
+
+        let row = field.sample(1, 5, 1.0, &mut generator);
+        assert_eq!(row.len(), 1);
+        assert_eq!(row[0].len(), 5);
+
+        let column = field.sample(5, 1, 1.0, &mut generator);
+        assert_eq!(column.len(), 5);
+        assert!(column.iter().all(|row| row.len() == 1));
+    }
+}
@@ -5,7 +5,8 @@ use probability::distribution::{Distribution, Gaussian};
 use probability::generator::Generator;
 
 use {Process, Stationary};
-use gaussian::circulant_embedding;
+use gaussian::{circulant_embedding, eigenvalues, Embedding};
+use dft::{Operation, Plan, Transform};
 
 macro_rules! hurst(
     ($value:expr) => ({
@@ -70,16 +71,114 @@ impl Noise {
     /// Generate a sample path.
     pub fn sample<G>(&self, points: usize, generator: &mut G) -> Vec<f64>
         where G: Generator
+    {
+        self.try_sample(points, generator).0
+    }
+
+    /// Generate a sample path, along with whether the underlying
+    /// circulant embedding was exact or only approximate.
+    ///
+    /// See `gaussian::Embedding`: exactness requires every eigenvalue of
+    /// the embedding circulant to be non-negative, which is not
+    /// guaranteed for fractional Gaussian noise, particularly for larger
+    /// Hurst exponents and certain lengths.
+    pub fn try_sample<G>(&self, points: usize, generator: &mut G) -> (Vec<f64>, Embedding)
+        where G: Generator
     {
         match points {
-            0 => vec![],
-            1 => vec![Gaussian::new(0.0, 1.0).sample(generator)],
+            0 => (vec![], Embedding::Exact),
+            1 => (vec![Gaussian::new(0.0, 1.0).sample(generator)], Embedding::Exact),
             _ => {
                 let n = points - 1;
                 let gaussian = Gaussian::new(0.0, 1.0);
-                let scale = (1.0 / n as f64).powf(self.hurst);
-                let data = circulant_embedding(self, n, || gaussian.sample(generator));
-                data.iter().take(points).map(|point| scale * point.re()).collect()
+                let (data, embedding) = circulant_embedding(self, n, || gaussian.sample(generator));
+                let path = data.iter().take(points).map(|point| point.re()).collect();
+                (path, embedding)
+            },
+        }
+    }
+}
+
+/// A fractional Gaussian noise prepared for repeated sampling.
+///
+/// Computing the eigenvalues of the circulant embedding takes one FFT
+/// over the autocovariance sequence, or several when the circulant has
+/// to be enlarged; see `gaussian::Embedding`. `PreparedNoise` performs this once
+/// and reuses it for every subsequent `sample`, which is worthwhile when
+/// many paths are needed for the same Hurst exponent, step, and length,
+/// e.g. for Monte Carlo or calibration.
+pub struct PreparedNoise {
+    points: usize,
+    m: usize,
+    radius: Vec<f64>,
+    embedding: Embedding,
+}
+
+impl Noise {
+    /// Prepare the noise for repeated sampling of paths of a given
+    /// length.
+    pub fn prepare(&self, points: usize) -> PreparedNoise {
+        match points {
+            0 | 1 => {
+                PreparedNoise {
+                    points: points,
+                    m: 0,
+                    radius: vec![],
+                    embedding: Embedding::Exact,
+                }
+            },
+            _ => {
+                let n = points - 1;
+                let (m, lambda, embedding) = eigenvalues(self, n);
+                let radius = lambda.iter().map(|&value| (value / m as f64).sqrt()).collect();
+                PreparedNoise {
+                    points: points,
+                    m: m,
+                    radius: radius,
+                    embedding: embedding,
+                }
+            },
+        }
+    }
+}
+
+impl PreparedNoise {
+    /// Whether the underlying circulant embedding is exact or only
+    /// approximate; see `gaussian::Embedding`.
+    #[inline]
+    pub fn embedding(&self) -> Embedding {
+        self.embedding
+    }
+
+    /// Generate a sample path.
+    pub fn sample<G>(&self, generator: &mut G) -> Vec<f64>
+        where G: Generator
+    {
+        self.sample_pair(generator).0
+    }
+
+    /// Generate two independent sample paths at once.
+    ///
+    /// The real and imaginary parts of the inverse transform of the
+    /// spectrum are themselves independent Gaussian samples with the
+    /// target covariance, so a single pass over the spectrum yields two
+    /// paths for the price of one.
+    pub fn sample_pair<G>(&self, generator: &mut G) -> (Vec<f64>, Vec<f64>)
+        where G: Generator
+    {
+        let gaussian = Gaussian::new(0.0, 1.0);
+        match self.points {
+            0 => (vec![], vec![]),
+            1 => (vec![gaussian.sample(generator)], vec![gaussian.sample(generator)]),
+            _ => {
+                let mut data: Vec<Complex> = self.radius.iter().map(|&radius| {
+                    Complex::new(radius * gaussian.sample(generator), radius * gaussian.sample(generator))
+                }).collect();
+                let plan = Plan::new(Operation::Inverse, self.m);
+                data.transform(&plan);
+                let real = data.iter().take(self.points).map(|point| point.re()).collect();
+                let imag = data.iter().take(self.points).map(|point| point.im()).collect();
+                (real, imag)
             },
         }
     }
@@ -115,4 +214,35 @@ impl Stationary for Noise {
         0.5 * self.step.powf(power) * ((tau + 1.0).powf(power) - 2.0 * tau.powf(power) +
                                        (tau - 1.0).abs().powf(power))
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use probability::generator::Default;
+
+    use {Stationary};
+    use super::Noise;
+
+    #[test]
+    fn sample_variance_matches_target_covariance() {
+        let noise = Noise::new(0.5, 1.0);
+        let mut generator = Default::new(42);
+        let points = 4000;
+        let path = noise.sample(points, &mut generator);
+        let mean = path.iter().sum::<f64>() / points as f64;
+        let variance = path.iter().map(|&value| (value - mean).powi(2)).sum::<f64>() / points as f64;
+        let target = Stationary::cov(&noise, 0);
+        assert!((variance - target).abs() < 0.1 * target);
+    }
+
+    #[test]
+    fn sample_handles_minimal_nondegenerate_length() {
+        // `points == 2` drives the circulant embedding with the smallest
+        // possible autocovariance sequence, `n == 1`, which used to
+        // collapse the minimal embedding size to `0` and panic.
+        let noise = Noise::new(0.5, 1.0);
+        let mut generator = Default::new(42);
+        assert_eq!(noise.sample(2, &mut generator).len(), 2);
+        assert_eq!(noise.prepare(2).sample(&mut generator).len(), 2);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,161 @@
+//! Gaussian processes.
+
+pub use self::correlated::{CorrelatedMotion, CorrelatedNoise};
+pub use self::field::Field;
+pub use self::fractional::{Motion, Noise, PreparedNoise};
+
+mod correlated;
+mod field;
+mod fractional;
+
+use complex::Complex;
+use dft::{Operation, Plan, Transform};
+
+use Stationary;
+
+/// The largest multiple of the minimal embedding size, `2 * (n - 1)`,
+/// tried before falling back to the approximate embedding of Wood and
+/// Chan.
+const MAX_SIZE_FACTOR: usize = 8;
+
+/// Whether a circulant embedding reproduces the target autocovariance
+/// exactly or only approximately.
+///
+/// The Davies–Harte embedding is exact only when every eigenvalue of the
+/// embedding circulant is non-negative. When that fails even after
+/// enlarging the circulant, the negative eigenvalues are zeroed and the
+/// remaining spectrum is rescaled to preserve the total variance `Σ
+/// λ_k`, which is only an approximation of the requested autocovariance.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Embedding {
+    /// Every eigenvalue was non-negative.
+    Exact,
+    /// Some eigenvalues were negative and had to be zeroed.
+    Approximate,
+}
+
+/// Enlarge an embedding by repeatedly doubling a size factor, starting
+/// at `1`, until `eigenvalues(factor)` comes back all non-negative or
+/// `MAX_SIZE_FACTOR` is reached, in which case the negative eigenvalues
+/// are zeroed and the rest rescaled via `approximate`.
+///
+/// Shared by the one-dimensional circulant embedding (`eigenvalues`
+/// below) and the two-dimensional BCCB embedding (`gaussian::field`),
+/// each of which only needs to supply how to build and transform its
+/// own embedding at a given size factor.
+fn embed<F>(mut eigenvalues: F) -> (usize, Vec<f64>, Embedding)
+    where F: FnMut(usize) -> Vec<f64>
+{
+    let mut factor = 1;
+    loop {
+        let lambda = eigenvalues(factor);
+        if lambda.iter().all(|&value| value >= 0.0) {
+            return (factor, lambda, Embedding::Exact);
+        }
+        if factor >= MAX_SIZE_FACTOR {
+            return (factor, approximate(lambda), Embedding::Approximate);
+        }
+        factor *= 2;
+    }
+}
+
+/// Zero the negative eigenvalues of an embedding and rescale the
+/// remaining ones so that the total variance `Σ λ_k` is preserved.
+fn approximate(lambda: Vec<f64>) -> Vec<f64> {
+    let total: f64 = lambda.iter().sum();
+    let positive: f64 = lambda.iter().cloned().filter(|&value| value > 0.0).sum();
+    let scale = if positive > 0.0 { total / positive } else { 0.0 };
+    lambda.into_iter().map(|value| if value > 0.0 { scale * value } else { 0.0 }).collect()
+}
+
+/// Build the first row of the circulant of size `m` embedding the
+/// autocovariance sequence `r(0), …, r(n - 1)` of a stationary process,
+/// `c = [r(0), …, r(n - 1), 0, …, 0, r(n - 2), …, r(1)]`.
+fn circulant_row<S>(process: &S, n: usize, m: usize) -> Vec<Complex>
+    where S: Stationary<Index = usize>
+{
+    let mut row = vec![Complex::new(0.0, 0.0); m];
+    for k in 0..n {
+        row[k] = Complex::new(Stationary::cov(process, k), 0.0);
+    }
+    for k in 1..(n - 1) {
+        row[m - k] = Complex::new(Stationary::cov(process, k), 0.0);
+    }
+    row
+}
+
+/// Compute the eigenvalues of a circulant embedding of the
+/// autocovariance sequence of a stationary process, enlarging the
+/// circulant until every eigenvalue is non-negative or `embed`'s cap is
+/// hit.
+///
+/// The eigenvalues are the real discrete Fourier transform of the first
+/// row of the circulant; see `circulant_row`. The minimal size is `2 *
+/// (n - 1)`, clamped to at least `2` so that a single-point
+/// autocovariance sequence (`n == 1`) still embeds into a non-empty
+/// circulant; see `Embedding` for what happens when enlarging does not
+/// suffice.
+fn eigenvalues<S>(process: &S, n: usize) -> (usize, Vec<f64>, Embedding)
+    where S: Stationary<Index = usize>
+{
+    let minimum = (2 * (n - 1)).max(2);
+    let (factor, lambda, embedding) = embed(|factor| {
+        let m = minimum * factor;
+        let mut row = circulant_row(process, n, m);
+        let plan = Plan::new(Operation::Forward, m);
+        row.transform(&plan);
+        row.iter().map(|point| point.re()).collect()
+    });
+    (minimum * factor, lambda, embedding)
+}
+
+/// Embed the autocovariance sequence of a stationary process into a
+/// circulant matrix and generate a realization via the Davies–Harte
+/// method, falling back to the approximate method of `Embedding` when
+/// the embedding is not positive semi-definite.
+fn circulant_embedding<S, F>(process: &S, n: usize, mut gaussian: F) -> (Vec<Complex>, Embedding)
+    where S: Stationary<Index = usize>, F: FnMut() -> f64
+{
+    let (m, lambda, embedding) = eigenvalues(process, n);
+    (spectrum(&lambda, m, &mut gaussian), embedding)
+}
+
+/// Draw a complex Gaussian spectrum with the given eigenvalues and
+/// invert it to obtain a circulant-embedded sample.
+fn spectrum<F>(lambda: &[f64], m: usize, gaussian: &mut F) -> Vec<Complex>
+    where F: FnMut() -> f64
+{
+    let scale = 1.0 / (m as f64).sqrt();
+    let mut data: Vec<Complex> = lambda.iter().map(|&value| {
+        let radius = scale * value.sqrt();
+        Complex::new(radius * gaussian(), radius * gaussian())
+    }).collect();
+    let plan = Plan::new(Operation::Inverse, m);
+    data.transform(&plan);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{approximate, embed, Embedding};
+
+    #[test]
+    fn approximate_zeros_negatives_and_preserves_total_variance() {
+        let lambda = vec![3.0, -1.0, 2.0, -2.0];
+        let total: f64 = lambda.iter().sum();
+        let fixed = approximate(lambda);
+        assert!(fixed.iter().all(|&value| value >= 0.0));
+        assert!((fixed.iter().sum::<f64>() - total).abs() < 1e-9);
+    }
+
+    #[test]
+    fn embed_falls_back_to_approximate_when_enlarging_never_helps() {
+        // An eigenvalue spectrum with a negative entry that stays
+        // negative no matter how far the embedding is enlarged, forcing
+        // `embed` through every doubling up to its cap.
+        let (_, lambda, embedding) = embed(|_| vec![3.0, -1.0, 2.0, -2.0]);
+        assert_eq!(embedding, Embedding::Approximate);
+        assert!(lambda.iter().all(|&value| value >= 0.0));
+        assert!((lambda.iter().sum::<f64>() - 2.0).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,89 @@
+//! Jump-diffusion paths overlaying a compound Poisson process on a
+//! fractional Brownian motion.
+
+use probability::distribution::{Distribution, Poisson};
+use probability::generator::Generator;
+
+use gaussian::Motion;
+
+/// A fractional Brownian motion overlaid with a compound Poisson jump
+/// process, `fBm + Σ jumps`.
+pub struct JumpMotion<D> {
+    motion: Motion,
+    intensity: f64,
+    jump: D,
+}
+
+impl<D> JumpMotion<D> where D: Distribution<Value = f64> {
+    /// Create a jump-diffusion process from a fractional Brownian motion
+    /// of the given Hurst exponent, a jump intensity `λ`, and a
+    /// distribution of individual jump sizes.
+    pub fn new(hurst: f64, intensity: f64, jump: D) -> JumpMotion<D> {
+        debug_assert!(intensity >= 0.0);
+        JumpMotion { motion: Motion::new(hurst), intensity: intensity, jump: jump }
+    }
+
+    /// Generate a sample path, the sum of the continuous fractional
+    /// Brownian motion and the compound Poisson jump process.
+    pub fn sample<G>(&self, points: usize, step: f64, generator: &mut G) -> Vec<f64>
+        where G: Generator
+    {
+        let continuous = self.motion.sample(points, step, generator);
+        let jumps = self.sample_jumps(points, step, generator);
+        continuous.iter().zip(jumps.iter()).map(|(&c, &j)| c + j).collect()
+    }
+
+    /// Generate the pure jump component alone: the running total of the
+    /// compound Poisson process at each point in time.
+    pub fn sample_jumps<G>(&self, points: usize, step: f64, generator: &mut G) -> Vec<f64>
+        where G: Generator
+    {
+        match points {
+            0 => vec![],
+            _ => {
+                let poisson = Poisson::new(self.intensity * step);
+                let mut data = vec![0.0];
+                let mut total = 0.0;
+                for _ in 1..points {
+                    let count = poisson.sample(generator) as usize;
+                    for _ in 0..count {
+                        total += self.jump.sample(generator);
+                    }
+                    data.push(total);
+                }
+                data
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use probability::distribution::Gaussian;
+    use probability::generator::Default;
+
+    use super::JumpMotion;
+
+    #[test]
+    fn sample_equals_continuous_plus_jumps() {
+        let jump = JumpMotion::new(0.5, 2.0, Gaussian::new(0.0, 1.0));
+
+        // `sample` draws the continuous motion first and the jumps
+        // second from the same generator, so reproducing that draw
+        // order on one shared generator is the only way to recover the
+        // exact `continuous` and `jumps` that went into a given `path`.
+        let mut generator = Default::new(42);
+        let continuous = jump.motion.sample(10, 1.0, &mut generator);
+        let jumps = jump.sample_jumps(10, 1.0, &mut generator);
+
+        let mut generator = Default::new(42);
+        let path = jump.sample(10, 1.0, &mut generator);
+
+        assert_eq!(path.len(), 10);
+        assert_eq!(jumps.len(), 10);
+        assert_eq!(jumps[0], 0.0);
+        for i in 0..10 {
+            assert_eq!(path[i], continuous[i] + jumps[i]);
+        }
+    }
+}
@@ -0,0 +1,30 @@
+//! Stochastic processes.
+
+extern crate complex;
+extern crate dft;
+extern crate probability;
+
+pub mod fractal;
+pub mod gaussian;
+pub mod jump;
+pub mod sde;
+
+/// A stochastic process.
+pub trait Process {
+    /// The index set.
+    type Index;
+    /// The state space.
+    type State;
+
+    /// Compute the autocovariance between two points in time.
+    fn cov(&self, t: Self::Index, s: Self::Index) -> f64;
+}
+
+/// A wide-sense stationary process.
+pub trait Stationary {
+    /// The index set.
+    type Index;
+
+    /// Compute the autocovariance at a given lag.
+    fn cov(&self, tau: Self::Index) -> f64;
+}